@@ -0,0 +1,299 @@
+// Exports the current arm pose as a standalone glTF document, so a pose
+// configured in the demo can be opened in Blender or other DCC tools.
+use std::{fs, mem};
+
+use bevy::{
+    prelude::*,
+    render::mesh::{Indices, VertexAttributeValues},
+};
+use gltf_json as json;
+use json::validation::Checked::Valid;
+
+use crate::Transformable;
+
+pub const GLTF_FILE_NAME: &str = "arm_pose.gltf";
+const BIN_FILE_NAME: &str = "arm_pose.bin";
+
+struct PrimitiveData {
+    positions: Vec<[f32; 3]>,
+    indices: Vec<u32>,
+}
+
+fn extract_primitive(mesh: &Mesh) -> Option<PrimitiveData> {
+    let VertexAttributeValues::Float32x3(positions) =
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION)?.clone()
+    else {
+        return None;
+    };
+
+    let indices = match mesh.indices()? {
+        Indices::U16(idx) => idx.iter().map(|&i| i as u32).collect(),
+        Indices::U32(idx) => idx.clone(),
+    };
+
+    Some(PrimitiveData { positions, indices })
+}
+
+fn push_primitive_buffers(
+    root: &mut json::Root,
+    bin: &mut Vec<u8>,
+    primitive: &PrimitiveData,
+) -> json::mesh::Primitive {
+    // Positions accessor + buffer view.
+    let positions_offset = bin.len();
+    for p in &primitive.positions {
+        for component in p {
+            bin.extend_from_slice(&component.to_le_bytes());
+        }
+    }
+    let positions_view = root.push(json::buffer::View {
+        buffer: json::Index::new(0),
+        byte_length: (bin.len() - positions_offset) as u32,
+        byte_offset: Some(positions_offset as u32),
+        byte_stride: None,
+        extensions: Default::default(),
+        extras: Default::default(),
+        name: None,
+        target: Some(Valid(json::buffer::Target::ArrayBuffer)),
+    });
+
+    let (min, max) = bounding_box(&primitive.positions);
+    let positions_accessor = root.push(json::Accessor {
+        buffer_view: Some(positions_view),
+        byte_offset: Some(0),
+        count: primitive.positions.len() as u32,
+        component_type: Valid(json::accessor::GenericComponentType(
+            json::accessor::ComponentType::F32,
+        )),
+        extensions: Default::default(),
+        extras: Default::default(),
+        type_: Valid(json::accessor::Type::Vec3),
+        min: Some(json::Value::from(min.to_vec())),
+        max: Some(json::Value::from(max.to_vec())),
+        name: None,
+        normalized: false,
+        sparse: None,
+    });
+
+    // Indices accessor + buffer view.
+    let indices_offset = bin.len();
+    for i in &primitive.indices {
+        bin.extend_from_slice(&i.to_le_bytes());
+    }
+    let indices_view = root.push(json::buffer::View {
+        buffer: json::Index::new(0),
+        byte_length: (bin.len() - indices_offset) as u32,
+        byte_offset: Some(indices_offset as u32),
+        byte_stride: None,
+        extensions: Default::default(),
+        extras: Default::default(),
+        name: None,
+        target: Some(Valid(json::buffer::Target::ElementArrayBuffer)),
+    });
+
+    let indices_accessor = root.push(json::Accessor {
+        buffer_view: Some(indices_view),
+        byte_offset: Some(0),
+        count: primitive.indices.len() as u32,
+        component_type: Valid(json::accessor::GenericComponentType(
+            json::accessor::ComponentType::U32,
+        )),
+        extensions: Default::default(),
+        extras: Default::default(),
+        type_: Valid(json::accessor::Type::Scalar),
+        min: None,
+        max: None,
+        name: None,
+        normalized: false,
+        sparse: None,
+    });
+
+    let mut attributes = std::collections::BTreeMap::new();
+    attributes.insert(
+        Valid(json::mesh::Semantic::Positions),
+        positions_accessor,
+    );
+
+    json::mesh::Primitive {
+        attributes,
+        extensions: Default::default(),
+        extras: Default::default(),
+        indices: Some(indices_accessor),
+        material: None,
+        mode: Valid(json::mesh::Mode::Triangles),
+        targets: None,
+    }
+}
+
+fn bounding_box(positions: &[[f32; 3]]) -> ([f32; 3], [f32; 3]) {
+    let mut min = [f32::MAX; 3];
+    let mut max = [f32::MIN; 3];
+    for p in positions {
+        for axis in 0..3 {
+            min[axis] = min[axis].min(p[axis]);
+            max[axis] = max[axis].max(p[axis]);
+        }
+    }
+    (min, max)
+}
+
+/// Writes the current arm pose to `arm_pose.gltf` + `arm_pose.bin` in the
+/// current directory. One node per `Transformable`, nested under three
+/// chained joint nodes (one per segment `id`) to keep the arm's hierarchy.
+pub fn export_gltf(
+    transformables: &Query<(&Transform, &Transformable, &Handle<Mesh>)>,
+    meshes: &Assets<Mesh>,
+) -> std::io::Result<()> {
+    let mut root = json::Root {
+        asset: json::Asset {
+            version: "2.0".to_string(),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut bin: Vec<u8> = Vec::new();
+
+    let joint_nodes: Vec<json::Index<json::Node>> = (0..3)
+        .map(|_| {
+            root.push(json::Node {
+                ..Default::default()
+            })
+        })
+        .collect();
+    let mut joint_children: Vec<Vec<json::Index<json::Node>>> = vec![Vec::new(); 3];
+
+    for (transform, transformable, mesh_handle) in transformables.iter() {
+        let Some(mesh) = meshes.get(mesh_handle) else {
+            continue;
+        };
+        let Some(primitive) = extract_primitive(mesh) else {
+            continue;
+        };
+        let gltf_primitive = push_primitive_buffers(&mut root, &mut bin, &primitive);
+
+        let mesh_index = root.push(json::Mesh {
+            extensions: Default::default(),
+            extras: Default::default(),
+            name: None,
+            primitives: vec![gltf_primitive],
+            weights: None,
+        });
+
+        let node = root.push(json::Node {
+            mesh: Some(mesh_index),
+            translation: Some(transform.translation.into()),
+            // glTF stores rotations as [x, y, z, w], matching the component
+            // ordering `Quat::from_xyzw` uses.
+            rotation: Some(json::scene::UnitQuaternion([
+                transform.rotation.x,
+                transform.rotation.y,
+                transform.rotation.z,
+                transform.rotation.w,
+            ])),
+            scale: Some(transform.scale.into()),
+            ..Default::default()
+        });
+
+        joint_children[transformable.id].push(node);
+    }
+
+    for (joint, children) in joint_nodes.iter().zip(mem::take(&mut joint_children)) {
+        root.nodes[joint.value()].children = Some(children);
+    }
+
+    // Chain the three segment joints so the joint hierarchy is preserved:
+    // joint 0 is the scene root, joint 1 is its child, joint 2 is joint 1's child.
+    root.nodes[joint_nodes[0].value()]
+        .children
+        .get_or_insert_with(Vec::new)
+        .push(joint_nodes[1]);
+    root.nodes[joint_nodes[1].value()]
+        .children
+        .get_or_insert_with(Vec::new)
+        .push(joint_nodes[2]);
+
+    root.push(json::Scene {
+        extensions: Default::default(),
+        extras: Default::default(),
+        name: None,
+        nodes: vec![joint_nodes[0]],
+    });
+    root.scene = Some(json::Index::new(0));
+
+    root.buffers.push(json::Buffer {
+        byte_length: bin.len() as u32,
+        name: None,
+        uri: Some(BIN_FILE_NAME.to_string()),
+        extensions: Default::default(),
+        extras: Default::default(),
+    });
+
+    fs::write(BIN_FILE_NAME, &bin)?;
+    fs::write(
+        GLTF_FILE_NAME,
+        json::serialize::to_string_pretty(&root).map_err(std::io::Error::other)?,
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod push_primitive_buffers_tests {
+    use super::*;
+
+    fn test_root() -> json::Root {
+        json::Root {
+            asset: json::Asset {
+                version: "2.0".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn buffer_offsets_and_accessors_round_trip_through_json() {
+        let primitive = PrimitiveData {
+            positions: vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+            indices: vec![0, 1, 2],
+        };
+
+        let mut root = test_root();
+        let mut bin = Vec::new();
+        let gltf_primitive = push_primitive_buffers(&mut root, &mut bin, &primitive);
+
+        let expected_bin_len = primitive.positions.len() * 3 * 4 + primitive.indices.len() * 4;
+        assert_eq!(bin.len(), expected_bin_len);
+
+        for view in &root.buffer_views {
+            let end = view.byte_offset.unwrap_or(0) as usize + view.byte_length as usize;
+            assert!(end <= bin.len());
+        }
+
+        let positions_index = gltf_primitive.attributes[&Valid(json::mesh::Semantic::Positions)];
+        assert_eq!(
+            root.accessors[positions_index.value()].count as usize,
+            primitive.positions.len()
+        );
+        assert_eq!(
+            root.accessors[gltf_primitive.indices.unwrap().value()].count as usize,
+            primitive.indices.len()
+        );
+
+        let serialized = json::serialize::to_string_pretty(&root).expect("serialize gltf json");
+        let reparsed: json::Root = serde_json::from_str(&serialized).expect("reparse gltf json");
+        assert_eq!(reparsed.accessors.len(), root.accessors.len());
+        assert_eq!(reparsed.buffer_views.len(), root.buffer_views.len());
+        assert_eq!(
+            reparsed.accessors[positions_index.value()].count,
+            primitive.positions.len() as u32
+        );
+    }
+
+    #[test]
+    fn bounding_box_matches_source_positions() {
+        let positions = [[1.0, -2.0, 0.5], [-3.0, 4.0, 2.0], [0.0, 0.0, -1.0]];
+        let (min, max) = bounding_box(&positions);
+        assert_eq!(min, [-3.0, -2.0, -1.0]);
+        assert_eq!(max, [1.0, 4.0, 2.0]);
+    }
+}
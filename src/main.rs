@@ -1,3 +1,9 @@
+mod gltf_export;
+mod spacemouse;
+
+use spacemouse::SpaceMouseConnection;
+
+use bevy::math::{DQuat, DVec3};
 use bevy::prelude::*;
 use bevy_egui::{
     egui::{self, Slider, Ui},
@@ -5,6 +11,141 @@ use bevy_egui::{
 };
 use static_math::{self, DualQuaternion};
 
+// Minimal numeric abstraction so the dual-quaternion math below can be
+// written once and instantiated at either f32 or f64 precision, instead of
+// maintaining two copies of the same formulas.
+trait KinematicScalar: Copy + std::ops::Mul<Output = Self> {
+    const HALF: Self;
+    const ZERO: Self;
+
+    fn from_f32(value: f32) -> Self;
+    fn to_f32(self) -> f32;
+    fn sin_cos(self) -> (Self, Self);
+}
+
+impl KinematicScalar for f32 {
+    const HALF: Self = 0.5;
+    const ZERO: Self = 0.0;
+
+    fn from_f32(value: f32) -> Self {
+        value
+    }
+
+    fn to_f32(self) -> f32 {
+        self
+    }
+
+    fn sin_cos(self) -> (Self, Self) {
+        f32::sin_cos(self)
+    }
+}
+
+impl KinematicScalar for f64 {
+    const HALF: Self = 0.5;
+    const ZERO: Self = 0.0;
+
+    fn from_f32(value: f32) -> Self {
+        value as f64
+    }
+
+    fn to_f32(self) -> f32 {
+        self as f32
+    }
+
+    fn sin_cos(self) -> (Self, Self) {
+        f64::sin_cos(self)
+    }
+}
+
+// Bridges `Vec3`/`DVec3` so the screw-axis math only has to be written once.
+trait KinematicVec3: Copy {
+    type Scalar: KinematicScalar;
+
+    fn from_vec3(v: Vec3) -> Self;
+    fn normalize_or_zero(self) -> Self;
+    fn xyz(self) -> (Self::Scalar, Self::Scalar, Self::Scalar);
+}
+
+impl KinematicVec3 for Vec3 {
+    type Scalar = f32;
+
+    fn from_vec3(v: Vec3) -> Self {
+        v
+    }
+
+    fn normalize_or_zero(self) -> Self {
+        Vec3::normalize_or_zero(self)
+    }
+
+    fn xyz(self) -> (f32, f32, f32) {
+        (self.x, self.y, self.z)
+    }
+}
+
+impl KinematicVec3 for DVec3 {
+    type Scalar = f64;
+
+    fn from_vec3(v: Vec3) -> Self {
+        DVec3::new(v.x as f64, v.y as f64, v.z as f64)
+    }
+
+    fn normalize_or_zero(self) -> Self {
+        DVec3::normalize_or_zero(self)
+    }
+
+    fn xyz(self) -> (f64, f64, f64) {
+        (self.x, self.y, self.z)
+    }
+}
+
+// Bridges `Quat`/`DQuat` so `dq_from_ctrls` and the per-segment composition
+// can be generic over kinematics precision. `static_math::Quaternion<Scalar>`
+// conversions piggyback on the existing `InternalFrom` impls.
+trait KinematicQuat:
+    Copy + std::ops::Mul<Output = Self> + InternalFrom<static_math::Quaternion<Self::Scalar>>
+{
+    type Scalar: KinematicScalar;
+    type Vec3: KinematicVec3<Scalar = Self::Scalar>;
+
+    fn from_xyzw(x: Self::Scalar, y: Self::Scalar, z: Self::Scalar, w: Self::Scalar) -> Self;
+    fn xyzw(self) -> (Self::Scalar, Self::Scalar, Self::Scalar, Self::Scalar);
+    fn to_f32_quat(self) -> Quat;
+}
+
+impl KinematicQuat for Quat {
+    type Scalar = f32;
+    type Vec3 = Vec3;
+
+    fn from_xyzw(x: f32, y: f32, z: f32, w: f32) -> Self {
+        Quat::from_xyzw(x, y, z, w)
+    }
+
+    fn xyzw(self) -> (f32, f32, f32, f32) {
+        (self.x, self.y, self.z, self.w)
+    }
+
+    fn to_f32_quat(self) -> Quat {
+        self
+    }
+}
+
+impl KinematicQuat for DQuat {
+    type Scalar = f64;
+    type Vec3 = DVec3;
+
+    fn from_xyzw(x: f64, y: f64, z: f64, w: f64) -> Self {
+        DQuat::from_xyzw(x, y, z, w)
+    }
+
+    fn xyzw(self) -> (f64, f64, f64, f64) {
+        (self.x, self.y, self.z, self.w)
+    }
+
+    fn to_f32_quat(self) -> Quat {
+        Quat::from_xyzw(self.x as f32, self.y as f32, self.z as f32, self.w as f32)
+    }
+}
+
 #[derive(Clone)]
 struct DualQuatCtrls {
     theta: f32,
@@ -15,16 +156,413 @@ struct DualQuatCtrls {
 impl Default for DualQuatCtrls {
     fn default() -> Self {
         Self {
-            // Theta causes a weird singularity where the other transforms seem to depend on it for transformation magnitude,
-            // but it also transforms the segment position across other axes.
-            // Setting it to 0.001 and magnifying inputs seemed like the best way to get the demo usable for now
-            theta: 0.001,
+            theta: 0.0,
             rot: Vec3::default(),
             rigid_body_comps: Vec3::default(),
         }
     }
 }
 
+// Builds a unit dual quaternion from screw parameters: real part is the
+// axis-angle rotation quaternion, dual part is `0.5 * (0, t) ⊗ q_r`. No
+// coupling between rotation and translation magnitude like the old
+// theta-scaled builder, and it's a unit dual quaternion by construction.
+//
+// `raw_dual`'s scalar (`rw` below) is generally nonzero, but it's exactly
+// the value the unit-dual-quaternion constraint (real · dual = 0) would
+// rederive from `real_quat` and the dual part's vector component, so
+// `new_from_array`'s 7-float layout dropping it isn't lossy.
+//
+// Generic over `Q` (`Quat`/`DQuat`) so f32 and f64 kinematics share this.
+fn dq_from_ctrls_generic<Q: KinematicQuat>(ctrls: &DualQuatCtrls) -> DualQuaternion<Q::Scalar> {
+    let normalized_rot = ctrls.rot.normalize_or_zero();
+    // A zero axis (e.g. `theta` moved without touching the rotation-axis
+    // sliders, which is the UI's actual default state) leaves the rotation
+    // axis undefined rather than implying "no rotation" on its own, so
+    // special-case it to the identity rotation instead of silently scaling
+    // `real_quat` down to `(0,0,0,cos(theta/2))`, which isn't unit length.
+    let real_quat = if normalized_rot == Vec3::ZERO {
+        Q::from_xyzw(Q::Scalar::ZERO, Q::Scalar::ZERO, Q::Scalar::ZERO, Q::Scalar::from_f32(1.0))
+    } else {
+        let axis = Q::Vec3::from_vec3(normalized_rot);
+        let angle = Q::Scalar::from_f32(ctrls.theta);
+        let (sin_half, cos_half) = (Q::Scalar::HALF * angle).sin_cos();
+        let (ax, ay, az) = axis.xyz();
+        Q::from_xyzw(ax * sin_half, ay * sin_half, az * sin_half, cos_half)
+    };
+
+    let translation = Q::Vec3::from_vec3(ctrls.rigid_body_comps);
+    let (tx, ty, tz) = translation.xyz();
+    let pure_translation = Q::from_xyzw(tx, ty, tz, Q::Scalar::ZERO);
+
+    let raw_dual = pure_translation * real_quat;
+    let (rx, ry, rz, rw) = raw_dual.xyzw();
+    let half = Q::Scalar::HALF;
+    let (qx, qy, qz, qw) = real_quat.xyzw();
+
+    DualQuaternion::new_from_array([
+        qx,
+        qy,
+        qz,
+        qw,
+        half * rx,
+        half * ry,
+        half * rz,
+    ])
+}
+
+fn dq_from_ctrls(ctrls: &DualQuatCtrls) -> DualQuaternion<f32> {
+    dq_from_ctrls_generic::<Quat>(ctrls)
+}
+
+// Matches a segment `id` to the dual quaternion chain that composes its
+// ancestors' transforms, e.g. id 2 is `base * dq1 * dq2 * dq3`.
+fn chain_dual_quat<Q: KinematicQuat>(
+    id: usize,
+    base_dual_quat: DualQuaternion<Q::Scalar>,
+    dq1: DualQuaternion<Q::Scalar>,
+    dq2: DualQuaternion<Q::Scalar>,
+    dq3: DualQuaternion<Q::Scalar>,
+) -> DualQuaternion<Q::Scalar> {
+    match id {
+        0 => base_dual_quat * dq1,
+        1 => base_dual_quat * dq1 * dq2,
+        2 => base_dual_quat * dq1 * dq2 * dq3,
+        _ => {
+            panic!("wrong id gfy");
+        }
+    }
+}
+
+// Returns the vector part of `dual * conj(real)`, which is half the
+// translation a unit dual quaternion encodes (the standard formula is
+// `2 * dual * conj(real)`); callers multiply the result by 2. `dual()` alone
+// is only the half translation when `real()` is the identity rotation.
+fn dual_quat_translation<Q: KinematicQuat>(
+    dq: DualQuaternion<Q::Scalar>,
+) -> (Q::Scalar, Q::Scalar, Q::Scalar) {
+    let real = dq.real();
+    let (tx, ty, tz, _) = Q::ext_from(dq.dual() * real.conj()).xyzw();
+    (tx, ty, tz)
+}
+
+// Runs the per-segment dual-quaternion composition for `transform_ui`,
+// generic over precision, so the f32 and f64 kinematics modes share one
+// code path. Only the final `Transform` is downcast to f32.
+fn compose_segment_transform<Q: KinematicQuat>(
+    id: usize,
+    base_dual_quat: DualQuaternion<Q::Scalar>,
+    dq1: DualQuaternion<Q::Scalar>,
+    dq2: DualQuaternion<Q::Scalar>,
+    dq3: DualQuaternion<Q::Scalar>,
+) -> Transform {
+    let dq = chain_dual_quat::<Q>(id, base_dual_quat, dq1, dq2, dq3);
+    let rotation = Q::ext_from(dq.real()).to_f32_quat();
+    let (tx, ty, tz) = dual_quat_translation::<Q>(dq);
+
+    Transform {
+        rotation,
+        translation: Vec3::new(tx.to_f32(), ty.to_f32(), tz.to_f32()) * 2.0,
+        scale: Vec3::ONE,
+    }
+}
+
+// The f32-only counterpart of `compose_segment_transform`'s tail, used by the
+// ScLERP playback system which always blends in f32 (the keyframes it stores
+// are themselves f32 slider configurations).
+fn dual_quat_to_f32_transform(dq: DualQuaternion<f32>) -> Transform {
+    let (tx, ty, tz) = dual_quat_translation::<Quat>(dq);
+    Transform {
+        rotation: Quat::ext_from(dq.real()),
+        translation: Vec3::new(tx, ty, tz) * 2.0,
+        scale: Vec3::ONE,
+    }
+}
+
+fn v3_to_vec3(v: static_math::V3<f32>) -> Vec3 {
+    Vec3::new(v[0], v[1], v[2])
+}
+
+// Below which the screw axis is treated as undefined (pure translation);
+// `dual_quat_pow` falls back to lerping the translation instead.
+const SCLERP_ANGLE_EPSILON: f32 = 1e-4;
+
+// Raises a screw motion to the power `t`: decompose into screw parameters
+// (angle, axis, pitch `d`, moment), scale by `t`, re-exponentiate.
+fn dual_quat_pow(q: DualQuaternion<f32>, t: f32) -> DualQuaternion<f32> {
+    let q_r = q.real();
+    let q_d = q.dual();
+
+    let w = q_r.real();
+    let theta = 2.0 * w.clamp(-1.0, 1.0).acos();
+
+    if theta.abs() < SCLERP_ANGLE_EPSILON {
+        let translation = v3_to_vec3(q_d.imag()) * 2.0 * t;
+        // With an identity real part the dual vector is half the translation.
+        let dual_vec = translation * 0.5;
+        return DualQuaternion::new_from_array([
+            0.0,
+            0.0,
+            0.0,
+            1.0,
+            dual_vec.x,
+            dual_vec.y,
+            dual_vec.z,
+        ]);
+    }
+
+    let (sin_half, cos_half) = (theta / 2.0).sin_cos();
+    let axis = v3_to_vec3(q_r.imag()) / sin_half;
+    let d = -2.0 * q_d.real() / sin_half;
+    let moment = (v3_to_vec3(q_d.imag()) - axis * (d / 2.0 * cos_half)) / sin_half;
+
+    let theta_t = theta * t;
+    let d_t = d * t;
+    let (sin_half_t, cos_half_t) = (theta_t / 2.0).sin_cos();
+
+    let real_vec = axis * sin_half_t;
+    let dual_vec = axis * (d_t / 2.0 * cos_half_t) + moment * sin_half_t;
+
+    DualQuaternion::new_from_array([
+        real_vec.x,
+        real_vec.y,
+        real_vec.z,
+        cos_half_t,
+        dual_vec.x,
+        dual_vec.y,
+        dual_vec.z,
+    ])
+}
+
+// Screw-linear interpolation: constant speed along the screw motion from
+// `q1` to `q2`, rather than lerping each slider independently.
+fn sclerp(q1: DualQuaternion<f32>, q2: DualQuaternion<f32>, t: f32) -> DualQuaternion<f32> {
+    let relative = q1.conj() * q2;
+    q1 * dual_quat_pow(relative, t)
+}
+
+#[cfg(test)]
+mod sclerp_tests {
+    use super::*;
+
+    fn sample_dq() -> DualQuaternion<f32> {
+        dq_from_ctrls(&DualQuatCtrls {
+            theta: std::f32::consts::FRAC_PI_3,
+            rot: Vec3::new(1.0, 2.0, 3.0),
+            rigid_body_comps: Vec3::new(4.0, -5.0, 6.0),
+        })
+    }
+
+    fn assert_dq_eq(a: DualQuaternion<f32>, b: DualQuaternion<f32>) {
+        let (a_rot, b_rot) = (Quat::ext_from(a.real()), Quat::ext_from(b.real()));
+        assert!((a_rot.xyz() - b_rot.xyz()).length() < 1e-4);
+        assert!((a_rot.w - b_rot.w).abs() < 1e-4);
+        assert!((Quat::ext_from(a.dual()).xyz() - Quat::ext_from(b.dual()).xyz()).length() < 1e-4);
+    }
+
+    #[test]
+    fn sclerp_at_zero_is_start() {
+        let (q1, q2) = (sample_dq(), dq_from_ctrls(&DualQuatCtrls::default()));
+        assert_dq_eq(sclerp(q1, q2, 0.0), q1);
+    }
+
+    #[test]
+    fn sclerp_at_one_is_end() {
+        let (q1, q2) = (dq_from_ctrls(&DualQuatCtrls::default()), sample_dq());
+        assert_dq_eq(sclerp(q1, q2, 1.0), q2);
+    }
+
+    #[test]
+    fn sclerp_falls_back_to_lerp_near_identity() {
+        let q1 = dq_from_ctrls(&DualQuatCtrls {
+            theta: 0.0,
+            rot: Vec3::default(),
+            rigid_body_comps: Vec3::new(2.0, 0.0, 0.0),
+        });
+        let q2 = dq_from_ctrls(&DualQuatCtrls {
+            theta: 0.0,
+            rot: Vec3::default(),
+            rigid_body_comps: Vec3::new(4.0, 0.0, 0.0),
+        });
+        let translation = Quat::ext_from(sclerp(q1, q2, 0.5).dual()).xyz() * 2.0;
+        assert!((translation - Vec3::new(3.0, 0.0, 0.0)).length() < 1e-4);
+    }
+}
+
+// Composes one frame's device motion onto a segment's stored slider state:
+// builds a delta dual quaternion, composes it onto the current one, then
+// decomposes back into the axis-angle + translation form the sliders use.
+fn apply_spacemouse_delta(
+    ctrls: &mut DualQuatCtrls,
+    delta_translation: Vec3,
+    delta_rotation: Vec3,
+    sensitivity: f32,
+) {
+    let current_dq = dq_from_ctrls(ctrls);
+
+    let delta_ctrls = DualQuatCtrls {
+        theta: delta_rotation.length() * sensitivity,
+        rot: delta_rotation.normalize_or_zero(),
+        rigid_body_comps: delta_translation * sensitivity,
+    };
+    let delta_dq = dq_from_ctrls(&delta_ctrls);
+
+    let composed = delta_dq * current_dq;
+
+    let rotation = Quat::ext_from(composed.real());
+    let (axis, angle) = rotation.to_axis_angle();
+    let (tx, ty, tz) = dual_quat_translation::<Quat>(composed);
+    let translation = Vec3::new(tx, ty, tz) * 2.0;
+
+    ctrls.theta = angle;
+    ctrls.rot = axis;
+    ctrls.rigid_body_comps = translation;
+}
+
+#[cfg(test)]
+mod apply_spacemouse_delta_tests {
+    use super::*;
+
+    #[test]
+    fn translation_delta_composes_through_existing_rotation() {
+        let mut ctrls = DualQuatCtrls {
+            theta: std::f32::consts::FRAC_PI_2,
+            rot: Vec3::X,
+            rigid_body_comps: Vec3::ZERO,
+        };
+        apply_spacemouse_delta(&mut ctrls, Vec3::Y, Vec3::ZERO, 1.0);
+        assert!((ctrls.rigid_body_comps - Vec3::Y).length() < 1e-4);
+    }
+}
+
+// Each arm is offset from the previous one by a fixed amount, calculated from its ID.
+// TODO: replace with per-segment transform.
+fn arm_trans_for_id(id: usize) -> Transform {
+    match id {
+        0 => Transform::default(),
+        1.. => Transform::from_translation(Vec3::new(0.0, 0.0, id as f32 * 5.0)),
+    }
+}
+
+#[cfg(test)]
+mod dq_from_ctrls_tests {
+    use super::*;
+
+    #[test]
+    fn real_part_is_unit_length() {
+        let ctrls = DualQuatCtrls {
+            theta: 1.2,
+            rot: Vec3::new(1.0, 2.0, 3.0),
+            rigid_body_comps: Vec3::new(4.0, -5.0, 6.0),
+        };
+        let dq = dq_from_ctrls(&ctrls);
+        assert!((dq.real().norm() - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn pure_translation_yields_identity_rotation_and_expected_translation() {
+        let t = Vec3::new(1.0, -2.0, 3.0);
+        let ctrls = DualQuatCtrls {
+            theta: 0.0,
+            rot: Vec3::default(),
+            rigid_body_comps: t,
+        };
+        let dq = dq_from_ctrls(&ctrls);
+
+        let rotation = Quat::ext_from(dq.real());
+        assert!((rotation.w - 1.0).abs() < 1e-5);
+        assert!(rotation.xyz().length() < 1e-5);
+
+        let translation = Quat::ext_from(dq.dual()).xyz();
+        assert!((translation - 0.5 * t).length() < 1e-5);
+    }
+
+    #[test]
+    fn zero_rotation_axis_yields_identity_regardless_of_theta() {
+        let ctrls = DualQuatCtrls {
+            theta: 1.2,
+            rot: Vec3::ZERO,
+            rigid_body_comps: Vec3::new(1.0, 2.0, 3.0),
+        };
+        let dq = dq_from_ctrls(&ctrls);
+        let rotation = Quat::ext_from(dq.real());
+        assert!((rotation.w - 1.0).abs() < 1e-5);
+        assert!(rotation.xyz().length() < 1e-5);
+    }
+
+    #[test]
+    fn recovers_translation_with_non_identity_rotation() {
+        // Rotation and translation both nonzero along the same axis, so
+        // `raw_dual`'s dropped scalar is provably nonzero (it's minus the dot
+        // product of the translation and the rotation's vector part).
+        let ctrls = DualQuatCtrls {
+            theta: std::f32::consts::FRAC_PI_2,
+            rot: Vec3::X,
+            rigid_body_comps: Vec3::new(1.0, 0.0, 0.0),
+        };
+        let dq = dq_from_ctrls(&ctrls);
+        let real = dq.real();
+        let translation = Quat::ext_from(dq.dual() * real.conj()).xyz() * 2.0;
+        assert!((translation - ctrls.rigid_body_comps).length() < 1e-4);
+    }
+}
+
+// Chains the same small screw motion onto itself repeatedly in both
+// precisions; f64 kinematics exists to cut down the error creep `dq1*dq2*dq3`
+// accumulates in f32, so its unit-norm drift over a long chain should be no
+// worse than f32's.
+#[cfg(test)]
+mod precision_tests {
+    use super::*;
+
+    #[test]
+    fn f64_kinematics_drifts_less_than_f32_over_a_long_chain() {
+        let ctrls = DualQuatCtrls {
+            theta: 0.137,
+            rot: Vec3::new(0.3, 0.6, 0.1),
+            rigid_body_comps: Vec3::new(0.05, -0.02, 0.03),
+        };
+
+        let step_f32 = dq_from_ctrls_generic::<Quat>(&ctrls);
+        let mut chain_f32 = DualQuaternion::<f32>::one();
+        for _ in 0..2000 {
+            chain_f32 = chain_f32 * step_f32;
+        }
+
+        let step_f64 = dq_from_ctrls_generic::<DQuat>(&ctrls);
+        let mut chain_f64 = DualQuaternion::<f64>::one();
+        for _ in 0..2000 {
+            chain_f64 = chain_f64 * step_f64;
+        }
+
+        let f32_drift = (chain_f32.real().norm() - 1.0).abs();
+        let f64_drift = (chain_f64.real().norm() - 1.0).abs() as f32;
+        assert!(f64_drift <= f32_drift);
+    }
+}
+
+// A saved slider configuration for all three segments, used as an endpoint
+// for ScLERP animation playback.
+#[derive(Clone)]
+struct Keyframe {
+    dual_quat1: DualQuatCtrls,
+    dual_quat2: DualQuatCtrls,
+    dual_quat3: DualQuatCtrls,
+}
+
+impl Keyframe {
+    fn capture(ui_state: &UiState) -> Self {
+        Self {
+            dual_quat1: ui_state.dual_quat1.clone(),
+            dual_quat2: ui_state.dual_quat2.clone(),
+            dual_quat3: ui_state.dual_quat3.clone(),
+        }
+    }
+}
+
+// How long, in seconds, a keyframe-to-keyframe ScLERP playback takes.
+const SCLERP_PLAYBACK_SECONDS: f32 = 2.0;
+
 // This struct stores the values for the sliders, so that they persist between frames
 // As EGUI is immediate mode, we have to maintain the state of the GUI ourselves
 #[derive(Resource, Default, Clone)]
@@ -32,6 +570,21 @@ struct UiState {
     dual_quat1: DualQuatCtrls,
     dual_quat2: DualQuatCtrls,
     dual_quat3: DualQuatCtrls,
+    // When set, the kinematics pipeline runs in `DualQuaternion<f64>` /
+    // `DQuat` and only downcasts to f32 for the final `Transform`, avoiding
+    // the error creep that repeated f32 composition (`dq1 * dq2 * dq3`)
+    // accumulates along the chain.
+    high_precision: bool,
+    keyframe_a: Option<Keyframe>,
+    keyframe_b: Option<Keyframe>,
+    playing: bool,
+    playback_timer: Timer,
+    // Set by the "Export glTF" button; `export_gltf_system` consumes it.
+    export_requested: bool,
+    // Which segment's controls the 6-DOF device drives: 0/1/2 for
+    // `dual_quat1`/`dual_quat2`/`dual_quat3`.
+    spacemouse_target: usize,
+    spacemouse_sensitivity: f32,
 }
 
 #[derive(Component, Default, Debug)]
@@ -74,6 +627,21 @@ impl InternalFrom<static_math::Quaternion<f32>> for Quat {
     }
 }
 
+// f64 mirrors of the above, used by the double-precision kinematics mode.
+impl InternalFrom<DQuat> for static_math::Quaternion<f64> {
+    fn ext_from(quat: DQuat) -> Self {
+        static_math::Quaternion::new_from(quat.x, quat.y, quat.z, quat.w)
+    }
+}
+
+impl InternalFrom<static_math::Quaternion<f64>> for DQuat {
+    fn ext_from(quaternion: static_math::Quaternion<f64>) -> Self {
+        let real: f64 = quaternion.real();
+        let imaginary: static_math::V3<f64> = quaternion.imag();
+        DQuat::from_xyzw(imaginary[0], imaginary[1], imaginary[2], real)
+    }
+}
+
 // impl InternalFrom<static_math::matrix3x3::M33<f32>> for Mat3 {
 //     fn ext_from(static_mat3: static_math::matrix3x3::M33<f32>) -> Self {
 //         let s = static_mat3.get_rows();
@@ -127,9 +695,19 @@ fn main() {
         .add_plugins(bevy_egui::EguiPlugin)
         // Systems (functions that are called at regular intervals)
         .add_systems(Startup, setup)
-        .add_systems(Update, transform_ui)
+        .add_systems(
+            Update,
+            (
+                spacemouse_input,
+                transform_ui,
+                animate_playback,
+                export_gltf_system,
+            )
+                .chain(),
+        )
         // Resources (live data that can be accessed from any system)
         .init_resource::<UiState>()
+        .insert_resource(SpaceMouseConnection::connect())
         .run(); // Event loop etc occurs here
 }
 
@@ -238,28 +816,6 @@ impl TensorProdVec3 for Vec3 {
     }
 }
 
-trait SinVec3 {
-    fn sin(&self) -> Vec3;
-    fn cos(&self) -> Vec3;
-}
-
-impl SinVec3 for Vec3 {
-    fn sin(&self) -> Vec3 {
-        Vec3 {
-            x: self.x.sin(),
-            y: self.y.sin(),
-            z: self.z.sin(),
-        }
-    }
-    fn cos(&self) -> Vec3 {
-        Vec3 {
-            x: self.x.cos(),
-            y: self.y.cos(),
-            z: self.z.cos(),
-        }
-    }
-}
-
 // This is where the transform happens
 fn transform_ui(
     mut transformables: Query<(&mut Transform, &mut Transformable)>,
@@ -309,71 +865,169 @@ fn transform_ui(
         dual_quat_sliders(ui, &mut ui_state.dual_quat1);
         dual_quat_sliders(ui, &mut ui_state.dual_quat2);
         dual_quat_sliders(ui, &mut ui_state.dual_quat3);
+        ui.checkbox(
+            &mut ui_state.high_precision,
+            "f64 kinematics (reduces error creep across the chain)",
+        );
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            if ui.button("Set Keyframe A").clicked() {
+                ui_state.keyframe_a = Some(Keyframe::capture(&ui_state));
+            }
+            if ui.button("Set Keyframe B").clicked() {
+                ui_state.keyframe_b = Some(Keyframe::capture(&ui_state));
+            }
+            let can_play = ui_state.keyframe_a.is_some() && ui_state.keyframe_b.is_some();
+            if ui.add_enabled(can_play, egui::Button::new("Play")).clicked() {
+                ui_state.playback_timer = Timer::from_seconds(SCLERP_PLAYBACK_SECONDS, TimerMode::Once);
+                ui_state.playing = true;
+            }
+        });
+
+        ui.separator();
+        if ui.button("Export glTF").clicked() {
+            ui_state.export_requested = true;
+        }
+
+        ui.separator();
+        let target_label = |id: usize| match id {
+            0 => "Segment 1",
+            1 => "Segment 2",
+            _ => "Segment 3",
+        };
+        egui::ComboBox::from_label("SpaceMouse target")
+            .selected_text(target_label(ui_state.spacemouse_target))
+            .show_ui(ui, |ui| {
+                for id in 0..3 {
+                    ui.selectable_value(&mut ui_state.spacemouse_target, id, target_label(id));
+                }
+            });
+        ui.add(
+            Slider::new(&mut ui_state.spacemouse_sensitivity, 0.0..=1.0)
+                .text("SpaceMouse sensitivity"),
+        );
     });
 
-    // Closure function for computing dual quaternion values from control values
-    let dq_from_ctrls = |ctrls: &DualQuatCtrls| {
-        // Note that we are scaling up rotation by 100x to avoid some singularities with theta
-        let rot = ctrls.rot * 100.0;
-        let theta = ctrls.theta;
-
-        // Building up the dual quaternion in portions
-        let real_quat = ((theta * rot) / 2.0).sin();
-        let real_quat_w = (theta / 2.0).cos();
-        let imag_quat = (0.5 * ctrls.rigid_body_comps) * (theta / 2.0).cos();
-
-        // Final assembly, and spit it out
-        DualQuaternion::new_from_array([
-            // real quat refers to the roll/pitch/yaw of the axis.
-            real_quat.x,
-            real_quat.y,
-            real_quat.z,
-            // real quat w is how big of a turn after you get the axis to the new location.
-            real_quat_w,
-            // This is translation.
-            imag_quat.x,
-            imag_quat.y,
-            imag_quat.z,
-        ])
+    // Iterate over all transformables, composing the dual quaternion chain either
+    // in f32 or f64 depending on `high_precision`. Only `dq_from_ctrls_generic`'s
+    // scalar type differs between the two branches; everything downstream shares
+    // `compose_segment_transform`.
+    if ui_state.high_precision {
+        let dq1 = dq_from_ctrls_generic::<DQuat>(&ui_state.dual_quat1);
+        let dq2 = dq_from_ctrls_generic::<DQuat>(&ui_state.dual_quat2);
+        let dq3 = dq_from_ctrls_generic::<DQuat>(&ui_state.dual_quat3);
+        let base_dual_quat = DualQuaternion::<f64>::one();
+
+        for (mut transform, transformable) in &mut transformables {
+            let quat_trans = compose_segment_transform::<DQuat>(
+                transformable.id,
+                base_dual_quat,
+                dq1,
+                dq2,
+                dq3,
+            );
+            let arm_trans = arm_trans_for_id(transformable.id);
+            *transform = quat_trans * arm_trans * transformable.node_transform;
+        }
+    } else {
+        let dq1 = dq_from_ctrls(&ui_state.dual_quat1);
+        let dq2 = dq_from_ctrls(&ui_state.dual_quat2);
+        let dq3 = dq_from_ctrls(&ui_state.dual_quat3);
+        let base_dual_quat = DualQuaternion::<f32>::one();
+
+        for (mut transform, transformable) in &mut transformables {
+            let quat_trans =
+                compose_segment_transform::<Quat>(transformable.id, base_dual_quat, dq1, dq2, dq3);
+            let arm_trans = arm_trans_for_id(transformable.id);
+            *transform = quat_trans * arm_trans * transformable.node_transform;
+        }
+    }
+}
+
+// Drives "Play": ScLERPs each segment between its two stored keyframes for
+// the duration of the playback timer. Runs after `transform_ui` so its
+// result isn't immediately overwritten by the slider-driven transform.
+fn animate_playback(
+    time: Res<Time>,
+    mut ui_state: ResMut<UiState>,
+    mut transformables: Query<(&mut Transform, &mut Transformable)>,
+) {
+    if !ui_state.playing {
+        return;
+    }
+
+    let (Some(keyframe_a), Some(keyframe_b)) =
+        (ui_state.keyframe_a.clone(), ui_state.keyframe_b.clone())
+    else {
+        ui_state.playing = false;
+        return;
     };
 
-    // Compute dual quaternions from the control values
-    let dq1 = dq_from_ctrls(&ui_state.dual_quat1);
-    let dq2 = dq_from_ctrls(&ui_state.dual_quat2);
-    let dq3 = dq_from_ctrls(&ui_state.dual_quat3);
+    ui_state.playback_timer.tick(time.delta());
+    let t = ui_state.playback_timer.fraction();
 
     let base_dual_quat = DualQuaternion::<f32>::one();
+    let dq1_a = dq_from_ctrls(&keyframe_a.dual_quat1);
+    let dq2_a = dq_from_ctrls(&keyframe_a.dual_quat2);
+    let dq3_a = dq_from_ctrls(&keyframe_a.dual_quat3);
+    let dq1_b = dq_from_ctrls(&keyframe_b.dual_quat1);
+    let dq2_b = dq_from_ctrls(&keyframe_b.dual_quat2);
+    let dq3_b = dq_from_ctrls(&keyframe_b.dual_quat3);
 
-    // Iterate over all transformables
     for (mut transform, transformable) in &mut transformables {
-        // Transformable contains `id` and node_transform`
-        // Here I am matching against `id` to do dual quaternion multiplication
-        // TODO: replace logic with iterator & `Vec<DualQuaternion>`
-        let dq = match transformable.id {
-            0 => base_dual_quat * dq1,
-            1 => base_dual_quat * dq1 * dq2,
-            2 => base_dual_quat * dq1 * dq2 * dq3,
-            _ => {
-                panic!("wrong id gfy");
-            }
-        };
+        let chain_a =
+            chain_dual_quat::<Quat>(transformable.id, base_dual_quat, dq1_a, dq2_a, dq3_a);
+        let chain_b =
+            chain_dual_quat::<Quat>(transformable.id, base_dual_quat, dq1_b, dq2_b, dq3_b);
+        let blended = sclerp(chain_a, chain_b, t);
 
-        // This is where we build the Bevy transform from the dual quaternion
-        let quat_trans = Transform {
-            rotation: Quat::ext_from(dq.real()).normalize(),
-            translation: Quat::ext_from(dq.dual()).xyz(),
-            scale: Vec3::ONE,
-        };
+        let arm_trans = arm_trans_for_id(transformable.id);
+        *transform = dual_quat_to_f32_transform(blended) * arm_trans * transformable.node_transform;
+    }
 
-        // Finally, we are building the arm transform. Each arm is offset from the previous by a fixed amount, calculated from its ID
-        // TODO: replace with per-segment transform.
-        let arm_trans = match transformable.id {
-            0 => Transform::default(),
-            1.. => Transform::from_translation(Vec3::new(0.0, 0.0, transformable.id as f32 * 5.0)),
-            _ => panic!("crabs hatet his one neet trik"),
-        };
+    if ui_state.playback_timer.finished() {
+        ui_state.playing = false;
+        // Write keyframe B's values back into the live sliders, otherwise
+        // `transform_ui` renders the stale slider state next frame and the
+        // arm visibly snaps away from the animation's endpoint.
+        ui_state.dual_quat1 = keyframe_b.dual_quat1.clone();
+        ui_state.dual_quat2 = keyframe_b.dual_quat2.clone();
+        ui_state.dual_quat3 = keyframe_b.dual_quat3.clone();
+    }
+}
+
+// Drives "Export glTF": serializes the current arm pose once the button sets
+// `ui_state.export_requested`.
+fn export_gltf_system(
+    mut ui_state: ResMut<UiState>,
+    transformables: Query<(&Transform, &Transformable, &Handle<Mesh>)>,
+    meshes: Res<Assets<Mesh>>,
+) {
+    if !ui_state.export_requested {
+        return;
+    }
+    ui_state.export_requested = false;
 
-        // Build final transform
-        *transform = quat_trans * arm_trans * transformable.node_transform;
+    match gltf_export::export_gltf(&transformables, &meshes) {
+        Ok(()) => info!("exported arm pose to {}", gltf_export::GLTF_FILE_NAME),
+        Err(err) => error!("failed to export glTF: {err}"),
     }
 }
+
+// Reads the 6-DOF device (if any is connected) and composes its motion this
+// frame onto whichever segment's controls `ui_state.spacemouse_target` selects.
+fn spacemouse_input(mut connection: ResMut<SpaceMouseConnection>, mut ui_state: ResMut<UiState>) {
+    let Some(motion) = connection.poll_motion() else {
+        return;
+    };
+
+    let sensitivity = ui_state.spacemouse_sensitivity;
+    let ctrls = match ui_state.spacemouse_target {
+        0 => &mut ui_state.dual_quat1,
+        1 => &mut ui_state.dual_quat2,
+        _ => &mut ui_state.dual_quat3,
+    };
+
+    apply_spacemouse_delta(ctrls, motion.translation, motion.rotation, sensitivity);
+}
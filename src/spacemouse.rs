@@ -0,0 +1,132 @@
+// Client for a 6-DOF ("SpaceMouse"/NDOF) input device, read the same way
+// desktop NDOF managers do: by connecting to the `spnavd` daemon's plain-
+// protocol Unix socket, rather than pulling in a full vendor SDK for a
+// single demo feature.
+use std::io::{ErrorKind, Read};
+use std::os::unix::net::UnixStream;
+
+use bevy::prelude::*;
+
+const SPNAV_SOCKET_PATH: &str = "/var/run/spnav.sock";
+
+// `spnavd`'s plain-protocol motion event: a `0` type tag followed by 7
+// little-endian i32s (x, y, z, rx, ry, rz, period).
+const MOTION_EVENT_TYPE: i32 = 0;
+const MOTION_FRAME_LEN: usize = 4 * 8;
+
+/// One motion sample from the device, in its raw (unscaled) units.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SpaceMouseMotion {
+    pub translation: Vec3,
+    pub rotation: Vec3,
+}
+
+/// Holds the (possibly absent) connection to the `spnavd` socket. The device
+/// and daemon are both optional: if neither is present, `poll_motion` simply
+/// never reports any motion.
+///
+/// `buffer` carries over bytes left from a previous call: we poll this once
+/// per frame against a plain byte stream (not a framed/datagram socket), so a
+/// read can land mid-frame, and the leftover bytes have to stay queued until
+/// the rest of that frame arrives rather than being discarded.
+#[derive(Resource, Default)]
+pub struct SpaceMouseConnection {
+    stream: Option<UnixStream>,
+    buffer: Vec<u8>,
+}
+
+impl SpaceMouseConnection {
+    pub fn connect() -> Self {
+        let stream = UnixStream::connect(SPNAV_SOCKET_PATH)
+            .and_then(|stream| {
+                stream.set_nonblocking(true)?;
+                Ok(stream)
+            })
+            .ok();
+        Self {
+            stream,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Drains every pending event off the socket and returns the most recent
+    /// complete motion sample, if any arrived this call.
+    pub fn poll_motion(&mut self) -> Option<SpaceMouseMotion> {
+        let mut disconnected = false;
+        match self.stream.as_mut() {
+            Some(stream) => {
+                let mut chunk = [0u8; 256];
+                loop {
+                    match stream.read(&mut chunk) {
+                        Ok(0) => {
+                            disconnected = true;
+                            break;
+                        }
+                        Ok(n) => self.buffer.extend_from_slice(&chunk[..n]),
+                        Err(err) if err.kind() == ErrorKind::WouldBlock => break,
+                        Err(_) => {
+                            disconnected = true;
+                            break;
+                        }
+                    }
+                }
+            }
+            None => return None,
+        }
+        if disconnected {
+            // The daemon went away (or the device was unplugged); drop the
+            // connection so we stop polling a dead socket.
+            self.stream = None;
+        }
+
+        let mut latest = None;
+        while self.buffer.len() >= MOTION_FRAME_LEN {
+            let frame: Vec<u8> = self.buffer.drain(..MOTION_FRAME_LEN).collect();
+            let mut fields = frame
+                .chunks_exact(4)
+                .map(|bytes| i32::from_le_bytes(bytes.try_into().expect("4-byte chunk")));
+            let event_type = fields.next().expect("frame has a type tag");
+            if event_type == MOTION_EVENT_TYPE {
+                let axis: Vec<i32> = fields.collect();
+                latest = Some(SpaceMouseMotion {
+                    translation: Vec3::new(axis[0] as f32, axis[1] as f32, axis[2] as f32),
+                    rotation: Vec3::new(axis[3] as f32, axis[4] as f32, axis[5] as f32),
+                });
+            }
+        }
+
+        latest
+    }
+}
+
+#[cfg(test)]
+mod poll_motion_tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn reassembles_a_frame_split_across_calls() {
+        let (mut writer, reader) = UnixStream::pair().expect("create socket pair");
+        reader.set_nonblocking(true).expect("set nonblocking");
+        let mut connection = SpaceMouseConnection {
+            stream: Some(reader),
+            buffer: Vec::new(),
+        };
+
+        let mut frame = Vec::new();
+        for field in [MOTION_EVENT_TYPE, 10, 20, 30, 1, 2, 3, 0] {
+            frame.extend_from_slice(&field.to_le_bytes());
+        }
+        assert_eq!(frame.len(), MOTION_FRAME_LEN);
+
+        writer.write_all(&frame[..10]).expect("write first chunk");
+        assert!(connection.poll_motion().is_none());
+
+        writer.write_all(&frame[10..]).expect("write remaining chunk");
+        let motion = connection
+            .poll_motion()
+            .expect("motion reported once the frame is complete");
+        assert_eq!(motion.translation, Vec3::new(10.0, 20.0, 30.0));
+        assert_eq!(motion.rotation, Vec3::new(1.0, 2.0, 3.0));
+    }
+}